@@ -51,6 +51,15 @@ impl<T> Global<T> {
     pub fn load(&self, value: T) -> Option<T> {
         critical_section::with(|cs| self.inner.borrow(cs).replace(Some(value)))
     }
+
+    /// Take the value back out of the global, if one is present.
+    ///
+    /// This allows a peripheral that was previously [Self::load()]ed to be reclaimed
+    /// by the main thread, for example after the interrupt source that was using it
+    /// has been disabled.
+    pub fn take(&self) -> Option<T> {
+        critical_section::with(|cs| self.inner.borrow(cs).replace(None))
+    }
 }
 
 /// The local type for sharing things with an interrupt handler
@@ -75,6 +84,29 @@ impl<T> Interrupt<T> {
         });
         result
     }
+
+    /// Grab a mutable reference to the contents.
+    ///
+    /// If the value is empty, the contents are taken from the mutex-locked `global`.
+    /// Unlike [Self::get_or_init_with()], this returns `None` instead of panicking if
+    /// the value is empty locally *and* `global` is empty (it has never been loaded, or
+    /// its value has since been reclaimed via [Global::take()]).
+    pub fn try_get(&mut self, global: &Global<T>) -> Option<&mut T> {
+        if self.inner.is_none() {
+            self.inner = critical_section::with(|cs| global.inner.borrow(cs).replace(None));
+        }
+        self.inner.as_mut()
+    }
+
+    /// Give the locally-held value back to `global`, so it can be reclaimed by the
+    /// main thread (or loaded into a different `Interrupt`).
+    ///
+    /// Does nothing if this `Interrupt` is not currently holding a value.
+    pub fn release_back(&mut self, global: &Global<T>) {
+        if let Some(value) = self.inner.take() {
+            critical_section::with(|cs| global.inner.borrow(cs).replace(Some(value)));
+        }
+    }
 }
 
 #[cfg(test)]
@@ -100,6 +132,23 @@ mod tests {
         let local_ref = local.get_or_init_with(&GLOBAL_TEST);
         assert_eq!(local_ref.inner.get(), 0);
     }
+
+    #[test]
+    fn round_trip() {
+        static GLOBAL_TEST: Global<u32> = Global::empty();
+
+        let mut local: Interrupt<u32> = Interrupt::empty();
+        // Nothing has been loaded yet, so this must not panic.
+        assert!(local.try_get(&GLOBAL_TEST).is_none());
+
+        GLOBAL_TEST.load(42);
+        assert_eq!(local.try_get(&GLOBAL_TEST), Some(&mut 42));
+
+        // Hand the peripheral back to the main thread.
+        local.release_back(&GLOBAL_TEST);
+        assert_eq!(GLOBAL_TEST.take(), Some(42));
+        assert_eq!(GLOBAL_TEST.take(), None);
+    }
 }
 
 // End of file
@@ -3,6 +3,8 @@
 
 pub mod const_init;
 pub mod irq_sharing;
+pub mod once;
+pub mod region;
 pub mod uninit;
 
 #[cfg(feature = "cas")]
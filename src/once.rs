@@ -0,0 +1,234 @@
+//! A safe, race-free one-time initializer for uninitialized statics.
+//!
+//! [GroundedOnceCell] is built on top of [GroundedCell](crate::uninit::GroundedCell), and
+//! uses the [`critical-section`](https://crates.io/crates/critical-section) crate (also used
+//! by [crate::irq_sharing]) to guarantee that the contained value is initialized at most once,
+//! without requiring an allocator or Rust's `std`.
+
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use crate::uninit::GroundedCell;
+
+const UNINIT: u8 = 0;
+const RUNNING: u8 = 1;
+const INIT: u8 = 2;
+
+/// Resets `state` back to `UNINIT` when dropped, unless disarmed first.
+///
+/// Used to unwind a `RUNNING` cell back to `UNINIT` if the initializing closure
+/// panics, rather than leaving the cell permanently stuck in `RUNNING`.
+struct ResetOnDrop<'a> {
+    state: &'a AtomicU8,
+    armed: bool,
+}
+
+impl Drop for ResetOnDrop<'_> {
+    fn drop(&mut self) {
+        if self.armed {
+            self.state.store(UNINIT, Ordering::Release);
+        }
+    }
+}
+
+/// ## GroundedOnceCell
+///
+/// [GroundedOnceCell] is a type that contains a single `T`, which starts out
+/// uninitialized, and may be initialized exactly once at runtime.
+///
+/// Unlike [GroundedCell](crate::uninit::GroundedCell), access to the contained value is
+/// entirely safe: a small `UNINIT`/`RUNNING`/`INIT` state machine, synchronized with
+/// [`critical_section::with`], ensures that the initializing closure is run at most once,
+/// and that no reference to the value is ever handed out before it has been written.
+///
+/// ```rust
+/// use grounded::once::GroundedOnceCell;
+///
+/// static EXAMPLE: GroundedOnceCell<u32> = GroundedOnceCell::new();
+///
+/// let val: &'static u32 = EXAMPLE.get_or_init(|| 42);
+/// assert_eq!(*val, 42);
+///
+/// // Later calls just return the already-initialized value.
+/// let val2: &'static u32 = EXAMPLE.get_or_init(|| panic!("should not be called again"));
+/// assert_eq!(*val2, 42);
+/// ```
+pub struct GroundedOnceCell<T> {
+    cell: GroundedCell<T>,
+    state: AtomicU8,
+}
+
+unsafe impl<T: Sync> Sync for GroundedOnceCell<T> {}
+
+impl<T> GroundedOnceCell<T> {
+    /// Create a new, uninitialized `GroundedOnceCell`.
+    ///
+    /// ```rust
+    /// use grounded::once::GroundedOnceCell;
+    ///
+    /// static EXAMPLE: GroundedOnceCell<u32> = GroundedOnceCell::new();
+    /// ```
+    pub const fn new() -> Self {
+        Self {
+            cell: GroundedCell::uninit(),
+            state: AtomicU8::new(UNINIT),
+        }
+    }
+
+    /// Obtain a reference to the contained value, initializing it with `f` if this
+    /// is the first call.
+    ///
+    /// If another call to [Self::get_or_init()] or [Self::try_get_or_init()] is already
+    /// in the process of initializing this cell (for example, if `f` recursively calls
+    /// back into this same cell), this function panics rather than re-entering the
+    /// initializer. Use [Self::try_get_or_init()] if a panic is undesirable.
+    ///
+    /// If `f` panics, or this function panics due to reentrancy, the cell is reset
+    /// back to uninitialized rather than being left stuck: a later, unrelated call is
+    /// free to attempt initialization again.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if called reentrantly while this cell is being initialized, or if `f` panics.
+    pub fn get_or_init(&'static self, f: impl FnOnce() -> T) -> &'static T {
+        self.try_get_or_init(f)
+            .expect("GroundedOnceCell::get_or_init called reentrantly")
+    }
+
+    /// Obtain a reference to the contained value, initializing it with `f` if this
+    /// is the first call.
+    ///
+    /// Returns `None` instead of panicking if this cell is currently being initialized
+    /// by another call further up the stack.
+    ///
+    /// `f` is run *outside* of any critical section, so it is free to block on (or be
+    /// interrupted by) other interrupts or critical sections elsewhere in the program;
+    /// only the short state transitions before and after `f` runs hold one.
+    ///
+    /// If `f` panics (including the reentrancy panic from [Self::get_or_init()] itself),
+    /// the cell is reset back to uninitialized rather than being left permanently stuck:
+    /// a later, unrelated call is free to attempt initialization again.
+    pub fn try_get_or_init(&'static self, f: impl FnOnce() -> T) -> Option<&'static T> {
+        let should_init = critical_section::with(|_cs| match self.state.load(Ordering::Acquire) {
+            INIT => Some(false),
+            UNINIT => {
+                self.state.store(RUNNING, Ordering::Relaxed);
+                Some(true)
+            }
+            _ => None,
+        })?;
+
+        if should_init {
+            // Resets the state back to `UNINIT` if dropped while still armed, which
+            // only happens if `f` unwinds (panics). On the successful path below we
+            // disarm it before it can run.
+            let mut reset_on_unwind = ResetOnDrop {
+                state: &self.state,
+                armed: true,
+            };
+            let val = f();
+            reset_on_unwind.armed = false;
+
+            critical_section::with(|_cs| {
+                // SAFETY: we are the only one who has observed `UNINIT` and moved the
+                // state to `RUNNING`, so we have exclusive access to the cell, and no
+                // other reference to it has been handed out yet.
+                unsafe {
+                    self.cell.get().write(val);
+                }
+                self.state.store(INIT, Ordering::Release);
+            });
+        }
+
+        // SAFETY: we only reach here if the state was observed as `INIT`, either
+        // already, or because we just wrote the value and stored `INIT` ourselves.
+        Some(unsafe { &*self.cell.get() })
+    }
+
+    /// Obtain a reference to the contained value, if it has already been initialized.
+    ///
+    /// ```rust
+    /// use grounded::once::GroundedOnceCell;
+    ///
+    /// static EXAMPLE: GroundedOnceCell<u32> = GroundedOnceCell::new();
+    /// assert_eq!(EXAMPLE.get(), None);
+    /// EXAMPLE.get_or_init(|| 42);
+    /// assert_eq!(EXAMPLE.get(), Some(&42));
+    /// ```
+    pub fn get(&'static self) -> Option<&'static T> {
+        if self.state.load(Ordering::Acquire) == INIT {
+            // SAFETY: the state is `INIT`, so the cell has been written and will
+            // never be written to again.
+            Some(unsafe { &*self.cell.get() })
+        } else {
+            None
+        }
+    }
+
+    /// Attempt to set the contents of this cell to `val`, if it has not already
+    /// been initialized.
+    ///
+    /// Returns `Ok(())` if this call initialized the cell, or `Err(val)` (handing
+    /// `val` back to the caller) if the cell was already initialized, or is currently
+    /// in the process of being initialized elsewhere.
+    ///
+    /// ```rust
+    /// use grounded::once::GroundedOnceCell;
+    ///
+    /// static EXAMPLE: GroundedOnceCell<u32> = GroundedOnceCell::new();
+    /// assert_eq!(EXAMPLE.set(42), Ok(()));
+    /// assert_eq!(EXAMPLE.set(99), Err(99));
+    /// assert_eq!(EXAMPLE.get(), Some(&42));
+    /// ```
+    pub fn set(&'static self, val: T) -> Result<(), T> {
+        critical_section::with(|_cs| {
+            if self.state.load(Ordering::Acquire) == UNINIT {
+                // SAFETY: the state was observed as `UNINIT`, and we hold the
+                // critical section, so no other caller can be concurrently writing.
+                unsafe {
+                    self.cell.get().write(val);
+                }
+                self.state.store(INIT, Ordering::Release);
+                Ok(())
+            } else {
+                Err(val)
+            }
+        })
+    }
+}
+
+impl<T> Default for GroundedOnceCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panicking_init_resets_for_later_callers() {
+        static CELL: GroundedOnceCell<u32> = GroundedOnceCell::new();
+
+        let result = std::panic::catch_unwind(|| {
+            CELL.get_or_init(|| panic!("boom"));
+        });
+        assert!(result.is_err());
+
+        // A later, unrelated call must still be able to initialize the cell,
+        // rather than being permanently wedged at `RUNNING`.
+        assert_eq!(*CELL.get_or_init(|| 42), 42);
+    }
+
+    #[test]
+    fn reentrant_init_resets_for_later_callers() {
+        static CELL: GroundedOnceCell<u32> = GroundedOnceCell::new();
+
+        let result = std::panic::catch_unwind(|| {
+            CELL.get_or_init(|| *CELL.get_or_init(|| 1));
+        });
+        assert!(result.is_err());
+
+        assert_eq!(*CELL.get_or_init(|| 42), 42);
+    }
+}
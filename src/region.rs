@@ -0,0 +1,170 @@
+//! DMA/zero-copy split handles carved from a static array.
+//!
+//! [SplittableArrayCell] lets a backing array be partitioned, at runtime and without
+//! ever creating an aliasing full-array slice, into multiple non-overlapping owned
+//! region handles. This is the buffer-lending pattern used by systems like Tock's
+//! grant/DMA subsystem: a TX half can be handed to an interrupt while an RX half is
+//! kept on the main thread, with the non-overlap invariant enforced at runtime
+//! (via [`critical_section`]) instead of by the caller.
+
+use core::cell::RefCell;
+use core::ops::{Deref, DerefMut};
+
+use crate::uninit::GroundedArrayCell;
+
+#[derive(Clone, Copy, PartialEq)]
+struct Span {
+    start: usize,
+    end: usize,
+}
+
+impl Span {
+    fn overlaps(&self, other: &Span) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+}
+
+/// ## SplittableArrayCell
+///
+/// [SplittableArrayCell] wraps a [GroundedArrayCell] with a small, fixed-size table of
+/// claimed `[start, end)` element ranges, guarded by [`critical_section`]. Up to `SPANS`
+/// non-overlapping regions may be lent out at once via [Self::take_region()]; a claim
+/// that overlaps an already-outstanding region is refused rather than handed out.
+///
+/// ```rust
+/// use grounded::region::SplittableArrayCell;
+///
+/// static BUF: SplittableArrayCell<u8, 128, 4> = SplittableArrayCell::uninit();
+///
+/// let mut tx = BUF.take_region(0, 64, 0).unwrap();
+/// let mut rx = BUF.take_region(64, 64, 0).unwrap();
+/// assert!(BUF.take_region(32, 16, 0).is_none()); // overlaps `tx`
+///
+/// tx[0] = 1;
+/// rx[0] = 2;
+/// drop(tx);
+///
+/// // Once dropped, the region can be claimed again — each claim re-fills its range
+/// // with `fill`, so a fresh lease never observes a previous lease's data.
+/// let tx_again = BUF.take_region(0, 64, 9).unwrap();
+/// assert_eq!(tx_again[0], 9);
+/// ```
+pub struct SplittableArrayCell<T, const N: usize, const SPANS: usize> {
+    inner: GroundedArrayCell<T, N>,
+    claims: critical_section::Mutex<RefCell<[Option<Span>; SPANS]>>,
+}
+
+unsafe impl<T: Sync, const N: usize, const SPANS: usize> Sync for SplittableArrayCell<T, N, SPANS> {}
+
+impl<T, const N: usize, const SPANS: usize> SplittableArrayCell<T, N, SPANS> {
+    /// Create an uninitialized `SplittableArrayCell`, with no regions claimed.
+    pub const fn uninit() -> Self {
+        Self {
+            inner: GroundedArrayCell::uninit(),
+            claims: critical_section::Mutex::new(RefCell::new([None; SPANS])),
+        }
+    }
+
+    fn release_span(&'static self, span: Span) {
+        critical_section::with(|cs| {
+            let mut claims = self.claims.borrow(cs).borrow_mut();
+            if let Some(slot) = claims.iter_mut().find(|slot| **slot == Some(span)) {
+                *slot = None;
+            }
+        });
+    }
+}
+
+impl<T: 'static + Copy, const N: usize, const SPANS: usize> SplittableArrayCell<T, N, SPANS> {
+    /// Claim the `len` elements starting at `offset` as an exclusively-owned region.
+    ///
+    /// Every claimed element is first overwritten with `fill`, so the region handed
+    /// back is always fully initialized, even though the backing storage may never
+    /// have been written to before. `T: Copy` is required to make this fill step
+    /// possible without needing a per-element constructor closure.
+    ///
+    /// Returns `None` if `offset + len` is out of bounds, if the requested range
+    /// overlaps a region that is already claimed, or if all `SPANS` claim slots are
+    /// currently in use. The returned [RegionOwner] releases its claim automatically
+    /// when dropped, after which the same range may be claimed again.
+    pub fn take_region(
+        &'static self,
+        offset: usize,
+        len: usize,
+        fill: T,
+    ) -> Option<RegionOwner<'static, T, N, SPANS>> {
+        let end = offset.checked_add(len)?;
+        if end > N {
+            return None;
+        }
+        let new_span = Span { start: offset, end };
+
+        let claimed = critical_section::with(|cs| {
+            let mut claims = self.claims.borrow(cs).borrow_mut();
+            if claims.iter().flatten().any(|span| span.overlaps(&new_span)) {
+                return false;
+            }
+            match claims.iter_mut().find(|slot| slot.is_none()) {
+                Some(slot) => {
+                    *slot = Some(new_span);
+                    true
+                }
+                None => false,
+            }
+        });
+        if !claimed {
+            return None;
+        }
+
+        // SAFETY: the claim table guarantees this range does not overlap any other
+        // outstanding region, and will not until the returned `RegionOwner` is
+        // dropped and releases it back via `release_span`. Every element in the
+        // range is written below before the slice is exposed, so it is never
+        // possible to observe uninitialized memory through the resulting `&mut [T]`.
+        unsafe {
+            let mut ptr = self.inner.as_mut_ptr().add(offset);
+            let end_ptr = ptr.add(len);
+            while ptr != end_ptr {
+                ptr.write(fill);
+                ptr = ptr.add(1);
+            }
+        }
+        let slice = unsafe { self.inner.get_subslice_mut_unchecked(offset, len) };
+        Some(RegionOwner {
+            slice,
+            span: new_span,
+            owner: self,
+        })
+    }
+}
+
+/// An exclusively-owned, non-overlapping region of a [SplittableArrayCell], obtained
+/// via [SplittableArrayCell::take_region()].
+///
+/// Derefs to `&[T]`/`&mut [T]`. The claimed range is released back to the owning cell
+/// when this guard is dropped.
+pub struct RegionOwner<'a, T: 'static, const N: usize, const SPANS: usize> {
+    slice: &'a mut [T],
+    span: Span,
+    owner: &'static SplittableArrayCell<T, N, SPANS>,
+}
+
+impl<'a, T: 'static, const N: usize, const SPANS: usize> Deref for RegionOwner<'a, T, N, SPANS> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.slice
+    }
+}
+
+impl<'a, T: 'static, const N: usize, const SPANS: usize> DerefMut for RegionOwner<'a, T, N, SPANS> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.slice
+    }
+}
+
+impl<'a, T: 'static, const N: usize, const SPANS: usize> Drop for RegionOwner<'a, T, N, SPANS> {
+    fn drop(&mut self) {
+        self.owner.release_span(self.span);
+    }
+}
@@ -1,7 +1,11 @@
 //! Helpers for dealing with statics that are (potentially) uninitialized at the
 //! start of a program.
 
-use core::{cell::UnsafeCell, mem::MaybeUninit};
+use core::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
 /// ## GroundedCell
 ///
@@ -277,4 +281,261 @@ impl<T, const N: usize> GroundedArrayCell<T, N> {
     pub unsafe fn get_subslice_mut_unchecked(&'static self, offset: usize, len: usize) -> &'static mut [T] {
         core::slice::from_raw_parts_mut(self.as_mut_ptr().add(offset), len)
     }
+
+    /// Compute the element index of `ptr` within this cell's backing storage, if any.
+    ///
+    /// Returns `None` if `ptr` lies outside the backing storage, or does not fall on
+    /// an element boundary (e.g. it points partway into a multi-byte `T`).
+    ///
+    /// This is useful for drivers that hand raw pointers into DMA engines (or accept
+    /// them from untrusted callers) to cheaply confirm a pointer still refers to this
+    /// buffer, in the spirit of an "is this pointer inside my storage" bounds check.
+    ///
+    /// ```rust
+    /// use grounded::uninit::GroundedArrayCell;
+    ///
+    /// static EXAMPLE: GroundedArrayCell<u32, 16> = GroundedArrayCell::uninit();
+    /// let base: *mut u32 = EXAMPLE.as_mut_ptr();
+    ///
+    /// // In bounds, aligned to an element boundary.
+    /// assert_eq!(EXAMPLE.offset_of(base.wrapping_add(3)), Some(3));
+    ///
+    /// // Out of bounds: past the end of the 16-element array.
+    /// assert_eq!(EXAMPLE.offset_of(base.wrapping_add(16)), None);
+    ///
+    /// // Misaligned: 5 bytes in, not a multiple of `size_of::<u32>()`.
+    /// let misaligned = (base as *const u8).wrapping_add(5) as *const u32;
+    /// assert_eq!(EXAMPLE.offset_of(misaligned), None);
+    /// ```
+    pub fn offset_of(&'static self, ptr: *const T) -> Option<usize> {
+        let elem_size = core::mem::size_of::<T>();
+        if elem_size == 0 {
+            return None;
+        }
+        let base = self.as_mut_ptr() as usize;
+        let addr = ptr as usize;
+        let byte_offset = addr.checked_sub(base)?;
+        if byte_offset % elem_size != 0 {
+            return None;
+        }
+        let idx = byte_offset / elem_size;
+        if idx < N {
+            Some(idx)
+        } else {
+            None
+        }
+    }
+
+    /// Check whether `ptr` falls on an element boundary within this cell's backing storage.
+    ///
+    /// ```rust
+    /// use grounded::uninit::GroundedArrayCell;
+    ///
+    /// static EXAMPLE: GroundedArrayCell<u32, 16> = GroundedArrayCell::uninit();
+    /// let base: *mut u32 = EXAMPLE.as_mut_ptr();
+    ///
+    /// assert!(EXAMPLE.contains_ptr(base.wrapping_add(3))); // in bounds
+    /// assert!(!EXAMPLE.contains_ptr(base.wrapping_add(16))); // out of bounds
+    /// assert!(!EXAMPLE.contains_ptr((base as *const u8).wrapping_add(5) as *const u32)); // misaligned
+    /// ```
+    #[inline]
+    pub fn contains_ptr(&'static self, ptr: *const T) -> bool {
+        self.offset_of(ptr).is_some()
+    }
+
+    /// Check whether the `len`-element range starting at `ptr` falls entirely within
+    /// this cell's backing storage.
+    ///
+    /// Returns `true` only if `ptr` is aligned to an element boundary within this
+    /// cell, and `ptr + len` does not run past the end of the backing storage. A
+    /// completed DMA descriptor can be validated this way before the pointer and
+    /// length are turned back into a safe slice via [Self::get_subslice_unchecked()].
+    ///
+    /// ```rust
+    /// use grounded::uninit::GroundedArrayCell;
+    ///
+    /// static EXAMPLE: GroundedArrayCell<u32, 16> = GroundedArrayCell::uninit();
+    /// let base: *mut u32 = EXAMPLE.as_mut_ptr();
+    ///
+    /// assert!(EXAMPLE.contains_range(base.wrapping_add(4), 12)); // [4, 16) fits exactly
+    /// assert!(!EXAMPLE.contains_range(base.wrapping_add(4), 13)); // runs one past the end
+    ///
+    /// // Misaligned start.
+    /// let misaligned = (base as *const u8).wrapping_add(5) as *const u32;
+    /// assert!(!EXAMPLE.contains_range(misaligned, 4));
+    /// ```
+    pub fn contains_range(&'static self, ptr: *const T, len: usize) -> bool {
+        match self.offset_of(ptr) {
+            Some(offset) => match offset.checked_add(len) {
+                Some(end) => end <= N,
+                None => false,
+            },
+            None => false,
+        }
+    }
+}
+
+/// Number of bits tracked by a single tracking word of a [TrackedArrayCell].
+const BITS_PER_WORD: usize = usize::BITS as usize;
+
+/// ## TrackedArrayCell
+///
+/// [TrackedArrayCell] wraps a [GroundedArrayCell] with a companion bitset that records,
+/// per-element, whether that element currently holds a valid, initialized value. This
+/// lets callers build slab-like structures on top of a single static array without
+/// open-coding their own validity tracking around [GroundedArrayCell::get_element_unchecked()].
+///
+/// Const generic expressions (computing the number of tracking words as
+/// `(N + BITS - 1) / BITS` directly on the type) are not yet stable, so the number of
+/// `usize` tracking words is given explicitly as the second const generic parameter,
+/// `WORDS`. [Self::uninit()] panics if `WORDS` is too small to hold one bit per element.
+///
+/// A set bit implies the corresponding element was fully written, with `Release`
+/// ordering, before the bit was stored; [Self::get_element()] only ever hands out a
+/// reference after observing the bit set with `Acquire` ordering.
+///
+/// Note that [Self::set_element()] and [Self::clear_element()] are `unsafe`: the
+/// bitset only tracks *whether* an element is valid, not *how many* `&T` references
+/// to it are currently live, so overwriting or clearing an element out from under a
+/// reference obtained via [Self::get_element()] is the caller's responsibility to
+/// avoid.
+///
+/// ```rust
+/// use grounded::uninit::TrackedArrayCell;
+///
+/// static EXAMPLE: TrackedArrayCell<u32, 128, 2> = TrackedArrayCell::uninit();
+///
+/// assert_eq!(EXAMPLE.get_element(0), None);
+/// // SAFETY: no other reference to element 0 is live.
+/// assert_eq!(unsafe { EXAMPLE.set_element(0, 42) }, None);
+/// assert_eq!(EXAMPLE.get_element(0), Some(&42));
+/// assert_eq!(EXAMPLE.initialized_count(), 1);
+/// ```
+pub struct TrackedArrayCell<T, const N: usize, const WORDS: usize> {
+    inner: GroundedArrayCell<T, N>,
+    tracking: [AtomicUsize; WORDS],
+}
+
+unsafe impl<T: Sync, const N: usize, const WORDS: usize> Sync for TrackedArrayCell<T, N, WORDS> {}
+
+impl<T, const N: usize, const WORDS: usize> TrackedArrayCell<T, N, WORDS> {
+    /// Create an uninitialized `TrackedArrayCell`, with every element marked as invalid.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `WORDS` is too small to track `N` elements, i.e. if
+    /// `WORDS * usize::BITS < N`.
+    pub const fn uninit() -> Self {
+        assert!(
+            WORDS * BITS_PER_WORD >= N,
+            "WORDS is too small to track N elements"
+        );
+        // An interior-mutable `const` is intentional here: it is only ever used as
+        // the repeat operand of an array literal, never referred to by path, so it
+        // cannot be accidentally shared.
+        #[allow(clippy::declare_interior_mutable_const)]
+        const ZERO: AtomicUsize = AtomicUsize::new(0);
+        Self {
+            inner: GroundedArrayCell::uninit(),
+            tracking: [ZERO; WORDS],
+        }
+    }
+
+    fn word_and_mask(idx: usize) -> (usize, usize) {
+        (idx / BITS_PER_WORD, 1usize << (idx % BITS_PER_WORD))
+    }
+
+    /// Write `val` into the element at `idx`, and mark it as initialized.
+    ///
+    /// If the element was already initialized, the previous value is returned so
+    /// the caller can drop it, rather than silently leaking or overwriting it in place.
+    ///
+    /// ## Safety
+    ///
+    /// The caller **must** ensure that no reference to this element, obtained from a
+    /// prior call to [Self::get_element()], is still live. Overwriting an element out
+    /// from under such a reference (this function takes the old value out by-value,
+    /// which the caller may then drop or move) would leave that reference dangling.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `idx >= N`.
+    pub unsafe fn set_element(&'static self, idx: usize, val: T) -> Option<T> {
+        assert!(idx < N, "index out of bounds");
+        let (word_idx, mask) = Self::word_and_mask(idx);
+        critical_section::with(|_cs| {
+            let word = &self.tracking[word_idx];
+            let was_set = word.load(Ordering::Acquire) & mask != 0;
+            // SAFETY: `idx < N`, and the critical section guarantees no other
+            // `set_element` or `clear_element` call is concurrently writing this
+            // element or its tracking bit for the duration of this block.
+            let ptr = unsafe { self.inner.as_mut_ptr().add(idx) };
+            let prev = if was_set {
+                Some(unsafe { ptr.read() })
+            } else {
+                None
+            };
+            unsafe {
+                ptr.write(val);
+            }
+            word.fetch_or(mask, Ordering::Release);
+            prev
+        })
+    }
+
+    /// Obtain a reference to the element at `idx`, if it has been initialized.
+    ///
+    /// Returns `None` if `idx >= N`, or if the element has not been set (or has
+    /// since been cleared via [Self::clear_element()]).
+    pub fn get_element(&'static self, idx: usize) -> Option<&'static T> {
+        if idx >= N {
+            return None;
+        }
+        let (word_idx, mask) = Self::word_and_mask(idx);
+        if self.tracking[word_idx].load(Ordering::Acquire) & mask != 0 {
+            // SAFETY: the tracking bit is set, so this element was fully
+            // written by a prior `set_element` before that bit was stored.
+            Some(unsafe { self.inner.get_element_unchecked(idx) })
+        } else {
+            None
+        }
+    }
+
+    /// Clear the element at `idx`, reading the value back out so the caller can drop it.
+    ///
+    /// Returns `None` if `idx >= N`, or if the element was not currently initialized.
+    ///
+    /// ## Safety
+    ///
+    /// The caller **must** ensure that no reference to this element, obtained from a
+    /// prior call to [Self::get_element()], is still live: this function reads the
+    /// element out by value, which the caller may then drop or move, leaving any such
+    /// reference dangling.
+    pub unsafe fn clear_element(&'static self, idx: usize) -> Option<T> {
+        if idx >= N {
+            return None;
+        }
+        let (word_idx, mask) = Self::word_and_mask(idx);
+        critical_section::with(|_cs| {
+            let word = &self.tracking[word_idx];
+            let old = word.fetch_and(!mask, Ordering::AcqRel);
+            if old & mask != 0 {
+                // SAFETY: the tracking bit was set (and we just claimed the
+                // exclusive right to clear it), so this element holds a valid
+                // `T` that is ours to read out.
+                let ptr = unsafe { self.inner.as_mut_ptr().add(idx) };
+                Some(unsafe { ptr.read() })
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Count the number of elements currently marked as initialized.
+    pub fn initialized_count(&'static self) -> usize {
+        self.tracking
+            .iter()
+            .map(|word| word.load(Ordering::Acquire).count_ones() as usize)
+            .sum()
+    }
 }